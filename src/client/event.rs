@@ -0,0 +1,67 @@
+//! 本次新增的群生命周期 / 回执事件的具体结构体
+//!
+//! 其余事件结构体（`GroupMessageEvent`、`FriendRequestEvent` 等）定义在仓库别处，
+//! 这里只补上 `QEvent` 新增变体所引用、此前未落地的那几个。
+
+/// 群消息撤回事件
+#[derive(Clone, Debug)]
+pub struct GroupMessageRecallEvent {
+    pub group_code: i64,
+    pub author_uin: i64,
+    pub msg_seq: i32,
+    pub time: i32,
+}
+
+/// 成员退群事件，涵盖自己退群和被踢两种情况（`operator_uin` 为 `None` 表示自己退群）
+#[derive(Clone, Debug)]
+pub struct GroupLeaveEvent {
+    pub group_code: i64,
+    pub member_uin: i64,
+    pub operator_uin: Option<i64>,
+}
+
+/// 群解散事件
+#[derive(Clone, Debug)]
+pub struct GroupDisbandEvent {
+    pub group_code: i64,
+    pub operator_uin: i64,
+}
+
+/// 群名称修改事件
+#[derive(Clone, Debug)]
+pub struct GroupNameUpdateEvent {
+    pub group_code: i64,
+    pub name_new: String,
+    pub operator_uin: i64,
+}
+
+/// 成员管理员权限变更事件
+#[derive(Clone, Debug)]
+pub struct MemberPermissionChangeEvent {
+    pub group_code: i64,
+    pub member_uin: i64,
+    pub is_admin: bool,
+}
+
+/// 群戳一戳（拍一拍）事件
+#[derive(Clone, Debug)]
+pub struct GroupPokeEvent {
+    pub group_code: i64,
+    pub sender_uin: i64,
+    pub receiver_uin: i64,
+}
+
+/// 被挤下线事件
+#[derive(Clone, Debug)]
+pub struct KickedOfflineEvent {
+    pub msg: String,
+}
+
+/// 群消息发送成功的回执，用于确认自己发出的消息
+#[derive(Clone, Debug)]
+pub struct GroupMessageReceiptEvent {
+    pub group_code: i64,
+    pub seq: i32,
+    pub rand: i32,
+    pub time: i32,
+}
@@ -0,0 +1,120 @@
+use std::any::Any;
+use std::future::Future;
+
+use serde_json::Value;
+use tokio::sync::{mpsc, oneshot};
+
+/// 可以通过 [`BotHandle::call`] 提交给客户端 worker 循环执行的一次性请求
+///
+/// 具体的请求（发群消息、同意加群、禁言成员……）各自实现这个 trait，
+/// worker 循环拿到装箱的请求后，通过 [`ApiRequest::as_any`] 向下转型到具体类型执行，
+/// 再把结果通过配对的 `oneshot::Sender` 送回去。
+pub trait ApiRequest: Send {
+    /// 请求的名字，仅用于日志/调试
+    fn name(&self) -> &'static str;
+    /// 向下转型到具体请求类型，供 worker 循环 `downcast_ref`
+    fn as_any(&self) -> &dyn Any;
+}
+
+/// 发送群消息
+pub struct SendGroupMessage {
+    pub group_code: i64,
+    pub message: String,
+}
+
+impl ApiRequest for SendGroupMessage {
+    fn name(&self) -> &'static str {
+        "send_group_message"
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// 同意或拒绝一个加群申请
+pub struct ApproveGroupRequest {
+    pub group_code: i64,
+    pub req_uin: i64,
+    pub approve: bool,
+}
+
+impl ApiRequest for ApproveGroupRequest {
+    fn name(&self) -> &'static str {
+        "approve_group_request"
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// 禁言群成员
+pub struct MuteGroupMember {
+    pub group_code: i64,
+    pub member_uin: i64,
+    pub duration_secs: u32,
+}
+
+impl ApiRequest for MuteGroupMember {
+    fn name(&self) -> &'static str {
+        "mute_group_member"
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// 事件 `Handler` 之外的另一半：把“提交请求并等待回执”封装成一个简单接口
+///
+/// 现有的 `BroadcastSender`/`MpscSender` 等 channel 实现的 `Handler` 只能把事件
+/// 往外发，没法拿到处理结果。`BotHandle` 配合 [`run_worker`]，把请求连同一个
+/// `oneshot::Sender` 一起发过去，`call` 等待 worker 处理完并把回执传回来。
+#[derive(Clone)]
+pub struct BotHandle {
+    request_tx: mpsc::Sender<(Box<dyn ApiRequest>, oneshot::Sender<Value>)>,
+}
+
+impl BotHandle {
+    /// 创建一对配套的 `BotHandle` 和请求接收端，接收端交给 [`run_worker`] 驱动
+    pub fn channel(
+        buffer: usize,
+    ) -> (
+        Self,
+        mpsc::Receiver<(Box<dyn ApiRequest>, oneshot::Sender<Value>)>,
+    ) {
+        let (request_tx, request_rx) = mpsc::channel(buffer);
+        (Self { request_tx }, request_rx)
+    }
+
+    pub fn new(request_tx: mpsc::Sender<(Box<dyn ApiRequest>, oneshot::Sender<Value>)>) -> Self {
+        Self { request_tx }
+    }
+
+    /// 提交一个请求，等待客户端 worker 循环处理后的回执；
+    /// 通道关闭或 worker 丢弃了回执都会返回 `None`
+    pub async fn call(&self, request: impl ApiRequest + 'static) -> Option<Value> {
+        let (resp_tx, resp_rx) = oneshot::channel();
+        self.request_tx
+            .send((Box::new(request), resp_tx))
+            .await
+            .ok()?;
+        resp_rx.await.ok()
+    }
+}
+
+/// 驱动 [`BotHandle`] 请求通道的 worker 循环
+///
+/// 不断从 `request_rx` 取出请求，交给 `execute`（真正持有 `Client` 的一方，负责
+/// 按 `request.as_any()` 向下转型并发起实际的协议调用）处理，再把结果通过配对的
+/// `oneshot::Sender` 送回去；接收端丢弃了回执就忽略发送失败。
+pub async fn run_worker<F, Fut>(
+    mut request_rx: mpsc::Receiver<(Box<dyn ApiRequest>, oneshot::Sender<Value>)>,
+    execute: F,
+) where
+    F: Fn(Box<dyn ApiRequest>) -> Fut,
+    Fut: Future<Output = Value>,
+{
+    while let Some((request, resp_tx)) = request_rx.recv().await {
+        let response = execute(request).await;
+        let _ = resp_tx.send(response);
+    }
+}
@@ -6,8 +6,24 @@ use tokio::sync::{
 };
 
 use crate::client::event::{
-    FriendMessageRecallEvent, FriendRequestEvent, GroupMessageEvent, GroupMuteEvent,
-    GroupRequestEvent, NewFriendEvent, NewMemberEvent, PrivateMessageEvent,
+    FriendMessageRecallEvent, FriendRequestEvent, GroupDisbandEvent, GroupLeaveEvent,
+    GroupMessageEvent, GroupMessageReceiptEvent, GroupMessageRecallEvent, GroupMuteEvent,
+    GroupNameUpdateEvent, GroupPokeEvent, GroupRequestEvent, KickedOfflineEvent,
+    MemberPermissionChangeEvent, NewFriendEvent, NewMemberEvent, PrivateMessageEvent,
+};
+
+mod rich;
+pub use rich::{FromQEvent, ReceivedGroupMessage, RichHandler, SentGroupMessage};
+
+mod spawning;
+pub use spawning::{default_conversation_key, SpawnPolicy, SpawningHandler};
+
+mod reconnect;
+pub use reconnect::{ConnectionState, ReconnectHandler};
+
+mod bot_handle;
+pub use bot_handle::{
+    ApiRequest, ApproveGroupRequest, BotHandle, MuteGroupMember, SendGroupMessage, run_worker,
 };
 
 /// 所有需要外发的数据的枚举打包
@@ -36,11 +52,24 @@ pub enum QEvent {
     FriendMessageRecall(FriendMessageRecallEvent),
     /// 新好友
     NewFriend(NewFriendEvent),
+    /// 群消息撤回
+    GroupMessageRecall(GroupMessageRecallEvent),
+    /// 成员退群（包括自身退群、被踢）
+    GroupLeave(GroupLeaveEvent),
+    /// 群解散
+    GroupDisband(GroupDisbandEvent),
+    /// 群名称修改
+    GroupNameUpdate(GroupNameUpdateEvent),
+    /// 成员管理员权限变更
+    MemberPermissionChange(MemberPermissionChangeEvent),
+    /// 群戳一戳
+    GroupPoke(GroupPokeEvent),
+    /// 被挤下线
+    KickedOffline(KickedOfflineEvent),
+    /// 群消息发送成功的回执，用于确认自己发出的消息
+    GroupMessageReceipt(GroupMessageReceiptEvent),
     // FriendList(decoder::friendlist::FriendListResponse),
     // GroupMemberInfo(structs::GroupMemberInfo),
-
-    // 群消息发送成功事件 内部处理
-    // GroupMessageReceipt(GroupMessageReceiptEvent)
 }
 
 /// 处理外发数据的接口
@@ -67,8 +96,28 @@ pub trait Handler: Sync {
                 self.handle_friend_message_recall(friend_message_recall)
                     .await
             }
+            QEvent::GroupMessageRecall(group_message_recall) => {
+                self.handle_group_message_recall(group_message_recall).await
+            }
+            QEvent::GroupLeave(group_leave) => self.handle_group_leave(group_leave).await,
+            QEvent::GroupDisband(group_disband) => self.handle_group_disband(group_disband).await,
+            QEvent::GroupNameUpdate(group_name_update) => {
+                self.handle_group_name_update(group_name_update).await
+            }
+            QEvent::MemberPermissionChange(member_permission_change) => {
+                self.handle_member_permission_change(member_permission_change)
+                    .await
+            }
+            QEvent::GroupPoke(group_poke) => self.handle_group_poke(group_poke).await,
+            QEvent::KickedOffline(kicked_offline) => {
+                self.handle_kicked_offline(kicked_offline).await
+            }
+            QEvent::GroupMessageReceipt(group_message_receipt) => {
+                self.handle_group_message_receipt(group_message_receipt)
+                    .await
+            }
             QEvent::TcpConnect => self.handle_tcp_connect_event().await,
-            QEvent::TcpDisconnect => self.handle_tcp_connect_event().await,
+            QEvent::TcpDisconnect => self.handle_tcp_disconnect_event().await,
         }
     }
     async fn handle_login_event(&self, _uin: i64) {}
@@ -84,6 +133,19 @@ pub trait Handler: Sync {
     async fn handle_friend_message_recall(&self, _friend_message_recall: FriendMessageRecallEvent) {
     }
     async fn handle_new_friend(&self, _new_friend: NewFriendEvent) {}
+    async fn handle_group_message_recall(&self, _group_message_recall: GroupMessageRecallEvent) {}
+    async fn handle_group_leave(&self, _group_leave: GroupLeaveEvent) {}
+    async fn handle_group_disband(&self, _group_disband: GroupDisbandEvent) {}
+    async fn handle_group_name_update(&self, _group_name_update: GroupNameUpdateEvent) {}
+    async fn handle_member_permission_change(
+        &self,
+        _member_permission_change: MemberPermissionChangeEvent,
+    ) {
+    }
+    async fn handle_group_poke(&self, _group_poke: GroupPokeEvent) {}
+    async fn handle_kicked_offline(&self, _kicked_offline: KickedOfflineEvent) {}
+    async fn handle_group_message_receipt(&self, _group_message_receipt: GroupMessageReceiptEvent) {
+    }
 }
 
 /// 一个默认 Handler，只是把信息打印出来
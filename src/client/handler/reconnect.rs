@@ -0,0 +1,205 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rand::Rng;
+use tokio::sync::{broadcast, watch};
+
+use super::{Handler, QEvent};
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+const JITTER_MS: u64 = 500;
+
+/// 连接当前所处的状态，可通过 [`ReconnectHandler::subscribe_state`] 观察
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ConnectionState {
+    Connecting,
+    Online,
+    Offline,
+}
+
+/// 监听 `QEvent::TcpDisconnect` 并自动重连的 Handler
+///
+/// 收到断线事件后，以指数退避加抖动反复调用 `reconnect` 重跑登录流程，成功后
+/// 把 `QEvent::TcpConnect`/`QEvent::LoginEvent` 重新灌回内层 Handler，让下游
+/// 订阅者像正常上线一样感知这次重连。`reconnect` 返回 `Some(uin)` 视为登录成功。
+pub struct ReconnectHandler<H, F> {
+    inner: Arc<H>,
+    reconnect: F,
+    state_tx: watch::Sender<ConnectionState>,
+    kill_tx: broadcast::Sender<()>,
+    /// 断线触发和心跳触发的重连可能同时发生，用它保证同一时刻只有一个在跑
+    reconnecting: AtomicBool,
+}
+
+impl<H, F, Fut> ReconnectHandler<H, F>
+where
+    H: Handler + 'static,
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<i64>> + Send + 'static,
+{
+    pub fn new(inner: H, reconnect: F) -> Self {
+        let (state_tx, _) = watch::channel(ConnectionState::Offline);
+        let (kill_tx, _) = broadcast::channel(1);
+        Self {
+            inner: Arc::new(inner),
+            reconnect,
+            state_tx,
+            kill_tx,
+            reconnecting: AtomicBool::new(false),
+        }
+    }
+
+    /// 观察连接状态的变化
+    pub fn subscribe_state(&self) -> watch::Receiver<ConnectionState> {
+        self.state_tx.subscribe()
+    }
+
+    /// 发出终止信号，结束正在进行的重连循环和心跳任务
+    pub fn shutdown(&self) {
+        let _ = self.kill_tx.send(());
+    }
+
+    /// 以指数退避加抖动反复尝试重连，直到成功
+    async fn reconnect_loop(&self) {
+        let _ = self.state_tx.send(ConnectionState::Connecting);
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            if let Some(uin) = (self.reconnect)().await {
+                let _ = self.state_tx.send(ConnectionState::Online);
+                self.inner.handle(QEvent::TcpConnect).await;
+                self.inner.handle(QEvent::LoginEvent(uin)).await;
+                return;
+            }
+            let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..JITTER_MS));
+            tokio::time::sleep(backoff + jitter).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    /// 跑一次完整的重连：保证同一时刻只有一个实例在跑，并且能被 `shutdown` 打断
+    async fn run_reconnect(&self) {
+        if self
+            .reconnecting
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_err()
+        {
+            // 已经有一次重连在跑（心跳和断线事件撞在一起），这次直接跳过
+            return;
+        }
+        let mut kill_rx = self.kill_tx.subscribe();
+        tokio::select! {
+            _ = self.reconnect_loop() => {}
+            _ = kill_rx.recv() => {}
+        }
+        self.reconnecting.store(false, Ordering::SeqCst);
+    }
+
+    /// 启动一个心跳任务：定期检查连接状态，断线时顺带触发重连，收到终止信号后退出
+    pub fn spawn_heartbeat(self: &Arc<Self>, interval: Duration) {
+        let this = self.clone();
+        let mut kill_rx = self.kill_tx.subscribe();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        if *this.state_tx.borrow() == ConnectionState::Offline {
+                            this.run_reconnect().await;
+                        }
+                    }
+                    _ = kill_rx.recv() => break,
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<H, F, Fut> Handler for ReconnectHandler<H, F>
+where
+    H: Handler + 'static,
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Option<i64>> + Send + 'static,
+{
+    async fn handle(&self, event: QEvent) {
+        match event {
+            QEvent::TcpDisconnect => {
+                let _ = self.state_tx.send(ConnectionState::Offline);
+                self.inner.handle(QEvent::TcpDisconnect).await;
+                self.run_reconnect().await;
+            }
+            // 首次登录（非重连触发）也要把状态标记为 Online，否则心跳的第一次
+            // tick 会看到初始值 Offline，误以为掉线又去跑一遍重连流程
+            QEvent::TcpConnect => {
+                let _ = self.state_tx.send(ConnectionState::Online);
+                self.inner.handle(QEvent::TcpConnect).await;
+            }
+            QEvent::LoginEvent(uin) => {
+                let _ = self.state_tx.send(ConnectionState::Online);
+                self.inner.handle(QEvent::LoginEvent(uin)).await;
+            }
+            other => self.inner.handle(other).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::*;
+
+    struct NoopHandler;
+
+    #[async_trait]
+    impl Handler for NoopHandler {}
+
+    #[tokio::test]
+    async fn healthy_startup_keeps_heartbeat_from_reconnecting() {
+        let reconnect_calls = Arc::new(AtomicUsize::new(0));
+        let calls = reconnect_calls.clone();
+        let handler = Arc::new(ReconnectHandler::new(NoopHandler, move || {
+            let calls = calls.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Some(1)
+            }
+        }));
+
+        // 模拟一次正常（非重连触发）的登录
+        handler.handle(QEvent::TcpConnect).await;
+        handler.handle(QEvent::LoginEvent(1)).await;
+
+        handler.spawn_heartbeat(Duration::from_millis(20));
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        handler.shutdown();
+
+        assert_eq!(reconnect_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_reconnects_are_mutually_exclusive() {
+        let reconnect_calls = Arc::new(AtomicUsize::new(0));
+        let calls = reconnect_calls.clone();
+        let handler = Arc::new(ReconnectHandler::new(NoopHandler, move || {
+            let calls = calls.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                // 故意放慢，让另一次并发的 run_reconnect 有机会撞上来
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Some(1)
+            }
+        }));
+
+        let a = handler.clone();
+        let b = handler.clone();
+        tokio::join!(a.run_reconnect(), b.run_reconnect());
+
+        assert_eq!(reconnect_calls.load(Ordering::SeqCst), 1);
+    }
+}
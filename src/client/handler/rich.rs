@@ -0,0 +1,229 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::future::join_all;
+
+use super::{
+    FriendMessageRecallEvent, FriendRequestEvent, GroupDisbandEvent, GroupLeaveEvent,
+    GroupMessageEvent, GroupMessageReceiptEvent, GroupMessageRecallEvent, GroupMuteEvent,
+    GroupNameUpdateEvent, GroupPokeEvent, GroupRequestEvent, Handler, KickedOfflineEvent,
+    MemberPermissionChangeEvent, NewFriendEvent, NewMemberEvent, PrivateMessageEvent, QEvent,
+};
+
+/// 一个注册在 [`RichHandler`] 上的回调
+type BoxedCallback = Arc<dyn Fn(QEvent) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+
+/// 可以从 [`QEvent`] 中还原出的具体事件类型，用于 [`RichHandler::add_handler`] 按类型分发
+pub trait FromQEvent: Clone + Send + Sync + 'static {
+    /// 尝试从 `QEvent` 中取出自身，变体不匹配时返回 `None`
+    fn from_qevent(event: &QEvent) -> Option<Self>;
+}
+
+macro_rules! impl_from_qevent {
+    ($ty:ty, $($variant:ident),+) => {
+        impl FromQEvent for $ty {
+            fn from_qevent(event: &QEvent) -> Option<Self> {
+                match event {
+                    $(QEvent::$variant(e) => Some(e.clone()),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// 收到的群消息，供 [`RichHandler::add_handler`] 区分 [`SentGroupMessage`]
+///
+/// `GroupMessage`/`SelfGroupMessage` 两个 `QEvent` 变体都携带 `GroupMessageEvent`，
+/// 如果直接用 `GroupMessageEvent` 的 `TypeId` 分发，两个变体会被当成同一种事件，
+/// 注册给其中一个的回调也会在另一个触发，所以这里用变体各自的标记类型分开。
+#[derive(Clone)]
+pub struct ReceivedGroupMessage(pub GroupMessageEvent);
+
+/// 机器人自己发送的群消息
+#[derive(Clone)]
+pub struct SentGroupMessage(pub GroupMessageEvent);
+
+impl std::ops::Deref for ReceivedGroupMessage {
+    type Target = GroupMessageEvent;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::Deref for SentGroupMessage {
+    type Target = GroupMessageEvent;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromQEvent for ReceivedGroupMessage {
+    fn from_qevent(event: &QEvent) -> Option<Self> {
+        match event {
+            QEvent::GroupMessage(e) => Some(Self(e.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl FromQEvent for SentGroupMessage {
+    fn from_qevent(event: &QEvent) -> Option<Self> {
+        match event {
+            QEvent::SelfGroupMessage(e) => Some(Self(e.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl_from_qevent!(PrivateMessageEvent, PrivateMessage);
+impl_from_qevent!(GroupRequestEvent, GroupRequest);
+impl_from_qevent!(FriendRequestEvent, FriendRequest);
+impl_from_qevent!(NewMemberEvent, NewMember);
+impl_from_qevent!(GroupMuteEvent, GroupMute);
+impl_from_qevent!(FriendMessageRecallEvent, FriendMessageRecall);
+impl_from_qevent!(NewFriendEvent, NewFriend);
+impl_from_qevent!(GroupMessageRecallEvent, GroupMessageRecall);
+impl_from_qevent!(GroupLeaveEvent, GroupLeave);
+impl_from_qevent!(GroupDisbandEvent, GroupDisband);
+impl_from_qevent!(GroupNameUpdateEvent, GroupNameUpdate);
+impl_from_qevent!(MemberPermissionChangeEvent, MemberPermissionChange);
+impl_from_qevent!(GroupPokeEvent, GroupPoke);
+impl_from_qevent!(KickedOfflineEvent, KickedOffline);
+impl_from_qevent!(GroupMessageReceiptEvent, GroupMessageReceipt);
+
+/// 返回某个 `QEvent` 所携带的具体事件类型的 `TypeId`；不携带事件结构体的变体
+/// （`TcpConnect`、`TcpDisconnect`、`LoginEvent`）返回 `None`
+fn content_type_id(event: &QEvent) -> Option<TypeId> {
+    match event {
+        QEvent::GroupMessage(_) => Some(TypeId::of::<ReceivedGroupMessage>()),
+        QEvent::SelfGroupMessage(_) => Some(TypeId::of::<SentGroupMessage>()),
+        QEvent::PrivateMessage(_) => Some(TypeId::of::<PrivateMessageEvent>()),
+        QEvent::GroupRequest(_) => Some(TypeId::of::<GroupRequestEvent>()),
+        QEvent::FriendRequest(_) => Some(TypeId::of::<FriendRequestEvent>()),
+        QEvent::NewMember(_) => Some(TypeId::of::<NewMemberEvent>()),
+        QEvent::GroupMute(_) => Some(TypeId::of::<GroupMuteEvent>()),
+        QEvent::FriendMessageRecall(_) => Some(TypeId::of::<FriendMessageRecallEvent>()),
+        QEvent::NewFriend(_) => Some(TypeId::of::<NewFriendEvent>()),
+        QEvent::GroupMessageRecall(_) => Some(TypeId::of::<GroupMessageRecallEvent>()),
+        QEvent::GroupLeave(_) => Some(TypeId::of::<GroupLeaveEvent>()),
+        QEvent::GroupDisband(_) => Some(TypeId::of::<GroupDisbandEvent>()),
+        QEvent::GroupNameUpdate(_) => Some(TypeId::of::<GroupNameUpdateEvent>()),
+        QEvent::MemberPermissionChange(_) => Some(TypeId::of::<MemberPermissionChangeEvent>()),
+        QEvent::GroupPoke(_) => Some(TypeId::of::<GroupPokeEvent>()),
+        QEvent::KickedOffline(_) => Some(TypeId::of::<KickedOfflineEvent>()),
+        QEvent::GroupMessageReceipt(_) => Some(TypeId::of::<GroupMessageReceiptEvent>()),
+        QEvent::TcpConnect | QEvent::TcpDisconnect | QEvent::LoginEvent(_) => None,
+    }
+}
+
+/// 支持为同一事件类型注册多个独立回调的 Handler
+///
+/// 不同于实现整个 `Handler` trait 只能写一个大 `match`，这里允许按具体事件类型
+/// （如 `ReceivedGroupMessage`、`FriendRequestEvent`）分别挂载任意数量的回调，
+/// 分发时通过 `TypeId` 查表，命中的回调会用 `join_all` 并发跑完。
+#[derive(Default)]
+pub struct RichHandler {
+    handlers: HashMap<TypeId, Vec<BoxedCallback>>,
+}
+
+impl RichHandler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为事件类型 `E` 注册一个回调，同一类型可以注册多次
+    pub fn add_handler<E, F, Fut>(&mut self, callback: F)
+    where
+        E: FromQEvent,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let callback = Arc::new(callback);
+        let boxed: BoxedCallback = Arc::new(move |event: QEvent| {
+            let callback = callback.clone();
+            Box::pin(async move {
+                if let Some(e) = E::from_qevent(&event) {
+                    callback(e).await;
+                }
+            })
+        });
+        self.handlers
+            .entry(TypeId::of::<E>())
+            .or_default()
+            .push(boxed);
+    }
+}
+
+#[async_trait]
+impl Handler for RichHandler {
+    async fn handle(&self, event: QEvent) {
+        let type_id = match content_type_id(&event) {
+            Some(type_id) => type_id,
+            None => return,
+        };
+        if let Some(callbacks) = self.handlers.get(&type_id) {
+            join_all(callbacks.iter().map(|cb| cb(event.clone()))).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    fn sample_receipt() -> GroupMessageReceiptEvent {
+        GroupMessageReceiptEvent {
+            group_code: 1,
+            seq: 1,
+            rand: 1,
+            time: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn all_callbacks_registered_for_a_type_run_on_a_matching_event() {
+        let mut rich = RichHandler::new();
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..2 {
+            let hits = hits.clone();
+            rich.add_handler::<GroupMessageReceiptEvent, _, _>(move |_receipt| {
+                let hits = hits.clone();
+                async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+
+        rich.handle(QEvent::GroupMessageReceipt(sample_receipt()))
+            .await;
+
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn callback_does_not_fire_for_a_different_event_type() {
+        let mut rich = RichHandler::new();
+        let hits = Arc::new(AtomicUsize::new(0));
+
+        let hits_clone = hits.clone();
+        rich.add_handler::<KickedOfflineEvent, _, _>(move |_event| {
+            let hits = hits_clone.clone();
+            async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        rich.handle(QEvent::GroupMessageReceipt(sample_receipt()))
+            .await;
+
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use tokio::sync::{mpsc, Mutex, Semaphore};
+
+use super::{Handler, QEvent};
+
+/// [`SpawnPolicy::Ordered`] 下，一个会话 key 超过这么久没有新消息就回收对应的任务，
+/// 可以用 [`SpawningHandler::with_idle_timeout`] 覆盖
+const DEFAULT_ORDERED_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// [`SpawnPolicy::Ordered`] 下默认的会话 key 提取策略：群消息、自身群消息和加群申请按群号分组，
+/// 私聊消息按发送者 QQ 号、加好友申请按申请人 QQ 号分组；其余事件（包括登录、断线，
+/// 以及撤回、禁言、退群等其他群事件）一律返回 `None`，这类事件不保证顺序，直接独立 spawn。
+///
+/// 这只是默认策略，调用方可以在 [`SpawningHandler::with_key_fn`] 里传入自己的提取函数。
+pub fn default_conversation_key(event: &QEvent) -> Option<i64> {
+    match event {
+        QEvent::GroupMessage(e) | QEvent::SelfGroupMessage(e) => Some(e.message.group_code),
+        QEvent::PrivateMessage(e) => Some(e.message.from_uin),
+        QEvent::GroupRequest(e) => Some(e.request.group_code),
+        QEvent::FriendRequest(e) => Some(e.request.req_uin),
+        _ => None,
+    }
+}
+
+/// [`SpawningHandler`] 的并发调度策略
+pub enum SpawnPolicy {
+    /// 来一个事件就 spawn 一个任务，不做任何限制
+    Unbounded,
+    /// 用信号量限制同时在跑的任务数
+    Bounded(Arc<Semaphore>),
+    /// 按会话 key（群号/好友 QQ 号）串行化，不同会话之间仍然并行
+    Ordered,
+}
+
+/// 包一层 `Handler`，把每个事件的处理 spawn 到独立 tokio 任务上
+///
+/// 默认的 `Handler::handle` 是内联 await 的，一个慢的 `handle_group_message`
+/// 会卡住整条接收循环。套上这一层之后，可以按 [`SpawnPolicy`] 选择完全并发、
+/// 限流并发，或者保证同一会话内消息顺序的并发。
+pub struct SpawningHandler<H, K> {
+    inner: Arc<H>,
+    policy: SpawnPolicy,
+    key_fn: K,
+    idle_timeout: Duration,
+    ordered_senders: Arc<Mutex<HashMap<i64, mpsc::Sender<QEvent>>>>,
+}
+
+impl<H> SpawningHandler<H, fn(&QEvent) -> Option<i64>>
+where
+    H: Handler + 'static,
+{
+    /// 用默认的会话 key 提取策略（按群号/好友 QQ 号分组）创建
+    pub fn new(inner: H, policy: SpawnPolicy) -> Self {
+        Self::with_key_fn(inner, policy, default_conversation_key)
+    }
+}
+
+impl<H, K> SpawningHandler<H, K>
+where
+    H: Handler + 'static,
+    K: Fn(&QEvent) -> Option<i64> + Send + Sync + 'static,
+{
+    /// 用自定义的会话 key 提取策略创建，仅 [`SpawnPolicy::Ordered`] 会用到它
+    pub fn with_key_fn(inner: H, policy: SpawnPolicy, key_fn: K) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            policy,
+            key_fn,
+            idle_timeout: DEFAULT_ORDERED_IDLE_TIMEOUT,
+            ordered_senders: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// 覆盖 [`SpawnPolicy::Ordered`] 回收空闲会话任务的超时时间
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    fn spawn_detached(&self, event: QEvent) {
+        let inner = self.inner.clone();
+        tokio::spawn(async move { inner.handle(event).await });
+    }
+
+    fn spawn_bounded(&self, semaphore: &Arc<Semaphore>, event: QEvent) {
+        let inner = self.inner.clone();
+        let semaphore = semaphore.clone();
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire().await;
+            inner.handle(event).await;
+        });
+    }
+
+    async fn spawn_ordered(&self, key: i64, event: QEvent) {
+        // 先在锁内把 Sender clone 出来，锁释放之后再 await send —— 不然一个 key 的
+        // 接收端处理慢了，拿着全局锁的 send 就会把所有其他 key 的 dispatch 也一起卡住。
+        let existing = { self.ordered_senders.lock().await.get(&key).cloned() };
+
+        let event = match existing {
+            Some(tx) => match tx.send(event).await {
+                Ok(()) => return,
+                // 接收端已经退出，把事件拿回来，重新起一个任务顶替
+                Err(mpsc::error::SendError(event)) => event,
+            },
+            None => event,
+        };
+
+        let (tx, mut rx) = mpsc::channel(32);
+        // 新建的 channel 容量肯定够放第一条消息，用 try_send 避免再次 await
+        let _ = tx.try_send(event);
+        let tx_for_identity = tx.clone();
+        self.ordered_senders.lock().await.insert(key, tx);
+
+        let inner = self.inner.clone();
+        let ordered_senders = self.ordered_senders.clone();
+        let idle_timeout = self.idle_timeout;
+        tokio::spawn(async move {
+            loop {
+                match tokio::time::timeout(idle_timeout, rx.recv()).await {
+                    Ok(Some(event)) => inner.handle(event).await,
+                    // 发送端都掉了，或者空闲太久了，回收这个会话的任务
+                    Ok(None) | Err(_) => break,
+                }
+            }
+            let mut senders = ordered_senders.lock().await;
+            // 回收前确认这个 key 还指向自己，避免把期间被别的调用顶替上去的新 channel 删掉
+            if senders
+                .get(&key)
+                .is_some_and(|current| current.same_channel(&tx_for_identity))
+            {
+                senders.remove(&key);
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl<H, K> Handler for SpawningHandler<H, K>
+where
+    H: Handler + 'static,
+    K: Fn(&QEvent) -> Option<i64> + Send + Sync + 'static,
+{
+    async fn handle(&self, event: QEvent) {
+        match &self.policy {
+            SpawnPolicy::Unbounded => self.spawn_detached(event),
+            SpawnPolicy::Bounded(semaphore) => self.spawn_bounded(semaphore, event),
+            SpawnPolicy::Ordered => match (self.key_fn)(&event) {
+                Some(key) => self.spawn_ordered(key, event).await,
+                None => self.spawn_detached(event),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use tokio::sync::Mutex as AsyncMutex;
+
+    use super::*;
+
+    /// 记录每次 `handle_login_event` 完成的顺序；`uin == 1` 的会话故意放慢，
+    /// 用来验证它不会拖慢其他会话。
+    struct RecordingHandler {
+        order: Arc<AsyncMutex<Vec<i64>>>,
+    }
+
+    #[async_trait]
+    impl Handler for RecordingHandler {
+        async fn handle_login_event(&self, uin: i64) {
+            if uin == 1 {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            self.order.lock().await.push(uin);
+        }
+    }
+
+    fn key_by_uin(event: &QEvent) -> Option<i64> {
+        match event {
+            QEvent::LoginEvent(uin) => Some(*uin),
+            _ => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn ordered_policy_runs_different_keys_in_parallel() {
+        let order = Arc::new(AsyncMutex::new(Vec::new()));
+        let handler = SpawningHandler::with_key_fn(
+            RecordingHandler {
+                order: order.clone(),
+            },
+            SpawnPolicy::Ordered,
+            key_by_uin,
+        );
+
+        handler.handle(QEvent::LoginEvent(1)).await;
+        handler.handle(QEvent::LoginEvent(2)).await;
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        // key 2 很快完成，不应该被 key 1 的慢会话卡住
+        assert_eq!(*order.lock().await, vec![2, 1]);
+    }
+
+    #[tokio::test]
+    async fn ordered_policy_evicts_idle_conversation() {
+        let order = Arc::new(AsyncMutex::new(Vec::new()));
+        let handler = SpawningHandler::with_key_fn(
+            RecordingHandler {
+                order: order.clone(),
+            },
+            SpawnPolicy::Ordered,
+            key_by_uin,
+        )
+        .with_idle_timeout(Duration::from_millis(20));
+
+        handler.handle(QEvent::LoginEvent(3)).await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(handler.ordered_senders.lock().await.len(), 1);
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(handler.ordered_senders.lock().await.len(), 0);
+    }
+}